@@ -0,0 +1,95 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks `Position` construction and asserts, via a counting global allocator, that
+//! 2D/3D positions really do stay off the heap.
+//!
+//! This only exercises `Position::from` directly, not a full `Geometry::from_object` parse.
+//! `util::read_position` fills a `Position` with `Position::push` instead, which uses the
+//! same inline storage for up to four coordinates, so no JSON fixture is needed here.
+
+#[macro_use]
+extern crate bencher;
+extern crate geojson;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use bencher::Bencher;
+use geojson::Position;
+
+struct CountingAllocator;
+
+static ALLOCATION_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATION_COUNT.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations_during<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATION_COUNT.load(Ordering::SeqCst);
+    f();
+    ALLOCATION_COUNT.load(Ordering::SeqCst) - before
+}
+
+fn position_2d_stays_off_the_heap(bench: &mut Bencher) {
+    bench.iter(|| {
+        let allocations = allocations_during(|| {
+            let pos = Position::from([1.1, 2.1]);
+            bencher::black_box(pos);
+        });
+        assert_eq!(allocations, 0, "a 2D Position must not allocate");
+    });
+}
+
+fn position_3d_stays_off_the_heap(bench: &mut Bencher) {
+    bench.iter(|| {
+        let allocations = allocations_during(|| {
+            let pos = Position::from([1.1, 2.1, 3.1]);
+            bencher::black_box(pos);
+        });
+        assert_eq!(allocations, 0, "a 3D Position must not allocate");
+    });
+}
+
+fn position_5d_spills_to_the_heap(bench: &mut Bencher) {
+    bench.iter(|| {
+        // Built outside `allocations_during` so only `Position::from`'s heap spill is
+        // measured, not the `Vec` literal itself.
+        let coords = vec![1.1, 2.1, 3.1, 4.1, 5.1];
+        let allocations = allocations_during(|| {
+            let pos = Position::from(coords);
+            bencher::black_box(pos);
+        });
+        assert_eq!(allocations, 1, "a 5D Position needs exactly one heap allocation");
+    });
+}
+
+benchmark_group!(
+    benches,
+    position_2d_stays_off_the_heap,
+    position_3d_stays_off_the_heap,
+    position_5d_spills_to_the_heap
+);
+benchmark_main!(benches);
@@ -0,0 +1,316 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversions between this crate's `Value`/`Geometry` and `geo_types`, gated behind the
+//! `geo-types` feature.
+//!
+//! The forward direction (`geo_types` -> `Value`) is infallible: every `geo_types` geometry
+//! has a direct GeoJSON equivalent. The reverse direction can fail, since a `Value` read off
+//! the wire might not have enough coordinates to form a valid position, line or ring; every
+//! such case returns
+//! [`Error::InvalidGeometryConversion`](enum.Error.html#variant.InvalidGeometryConversion).
+
+use std::convert::TryFrom;
+
+use ::geometry::{Geometry, Value};
+use ::position::{LineStringType, PolygonType, Position};
+use ::Error;
+
+impl<'a> From<&'a ::geo_types::Point<f64>> for Value {
+    fn from(point: &'a ::geo_types::Point<f64>) -> Value {
+        Value::Point([point.x(), point.y()].into())
+    }
+}
+
+impl<'a> From<&'a ::geo_types::MultiPoint<f64>> for Value {
+    fn from(multi_point: &'a ::geo_types::MultiPoint<f64>) -> Value {
+        Value::MultiPoint(multi_point.0.iter().map(|point| [point.x(), point.y()].into()).collect())
+    }
+}
+
+impl<'a> From<&'a ::geo_types::LineString<f64>> for Value {
+    fn from(line_string: &'a ::geo_types::LineString<f64>) -> Value {
+        Value::LineString(line_string.points_iter().map(|point| [point.x(), point.y()].into()).collect())
+    }
+}
+
+impl<'a> From<&'a ::geo_types::MultiLineString<f64>> for Value {
+    fn from(multi_line_string: &'a ::geo_types::MultiLineString<f64>) -> Value {
+        Value::MultiLineString(multi_line_string.0.iter().map(|line_string| {
+            line_string.points_iter().map(|point| [point.x(), point.y()].into()).collect()
+        }).collect())
+    }
+}
+
+impl<'a> From<&'a ::geo_types::Polygon<f64>> for Value {
+    fn from(polygon: &'a ::geo_types::Polygon<f64>) -> Value {
+        let mut rings = Vec::with_capacity(1 + polygon.interiors().len());
+        rings.push(polygon.exterior().points_iter().map(|point| [point.x(), point.y()].into()).collect());
+        for interior in polygon.interiors() {
+            rings.push(interior.points_iter().map(|point| [point.x(), point.y()].into()).collect());
+        }
+        Value::Polygon(rings)
+    }
+}
+
+impl<'a> From<&'a ::geo_types::MultiPolygon<f64>> for Value {
+    fn from(multi_polygon: &'a ::geo_types::MultiPolygon<f64>) -> Value {
+        Value::MultiPolygon(multi_polygon.0.iter().map(|polygon| {
+            Value::from(polygon).into_polygon_rings()
+        }).collect())
+    }
+}
+
+impl<'a> From<&'a ::geo_types::GeometryCollection<f64>> for Value {
+    fn from(geometry_collection: &'a ::geo_types::GeometryCollection<f64>) -> Value {
+        Value::GeometryCollection(geometry_collection.0.iter().map(|geometry| {
+            Geometry::new(Value::from_geo_types(geometry))
+        }).collect())
+    }
+}
+
+impl Value {
+    /// The GeoJSON type name for this value, used in conversion error messages.
+    fn type_name(&self) -> &'static str {
+        match *self {
+            Value::Point(..) => "Point",
+            Value::MultiPoint(..) => "MultiPoint",
+            Value::LineString(..) => "LineString",
+            Value::MultiLineString(..) => "MultiLineString",
+            Value::Polygon(..) => "Polygon",
+            Value::MultiPolygon(..) => "MultiPolygon",
+            Value::GeometryCollection(..) => "GeometryCollection",
+        }
+    }
+
+    fn into_polygon_rings(self) -> PolygonType {
+        match self {
+            Value::Polygon(rings) => rings,
+            _ => unreachable!("From<&geo_types::Polygon> always produces Value::Polygon"),
+        }
+    }
+
+    fn from_geo_types(geometry: &::geo_types::Geometry<f64>) -> Value {
+        match *geometry {
+            ::geo_types::Geometry::Point(ref g) => Value::from(g),
+            ::geo_types::Geometry::Line(ref g) => {
+                Value::LineString(vec![
+                    [g.start.x, g.start.y].into(),
+                    [g.end.x, g.end.y].into(),
+                ])
+            }
+            ::geo_types::Geometry::LineString(ref g) => Value::from(g),
+            ::geo_types::Geometry::Polygon(ref g) => Value::from(g),
+            ::geo_types::Geometry::MultiPoint(ref g) => Value::from(g),
+            ::geo_types::Geometry::MultiLineString(ref g) => Value::from(g),
+            ::geo_types::Geometry::MultiPolygon(ref g) => Value::from(g),
+            ::geo_types::Geometry::GeometryCollection(ref g) => Value::from(g),
+            ::geo_types::Geometry::Rect(ref g) => Value::from(&g.to_polygon()),
+            ::geo_types::Geometry::Triangle(ref g) => Value::from(&g.to_polygon()),
+        }
+    }
+}
+
+/// Reads a `Position`'s first two components into a `geo_types::Coordinate`, erroring
+/// instead of panicking if the position is too short to have an x and a y.
+fn position_to_coordinate(position: &Position) -> Result<::geo_types::Coordinate<f64>, Error> {
+    if position.len() < 2 {
+        return Err(Error::InvalidGeometryConversion(
+            "a position needs at least 2 coordinates".into()));
+    }
+    Ok(::geo_types::Coordinate { x: position[0], y: position[1] })
+}
+
+/// Converts a run of positions into a `geo_types::LineString`, requiring at least `min_len`
+/// positions (2 for a bare `LineString`, 4 for a ring: three distinct points plus the
+/// closing point).
+fn positions_to_line_string(
+    positions: &LineStringType, min_len: usize, what: &str,
+) -> Result<::geo_types::LineString<f64>, Error> {
+    if positions.len() < min_len {
+        return Err(Error::InvalidGeometryConversion(
+            format!("a {} needs at least {} positions", what, min_len)));
+    }
+    let coords = try!(positions.iter().map(position_to_coordinate).collect::<Result<Vec<_>, _>>());
+    Ok(::geo_types::LineString(coords))
+}
+
+/// Converts a `Polygon`'s rings (exterior first, then interiors) into a `geo_types::Polygon`.
+fn rings_to_polygon(rings: &PolygonType) -> Result<::geo_types::Polygon<f64>, Error> {
+    let exterior_ring = try!(rings.get(0).ok_or_else(|| {
+        Error::InvalidGeometryConversion("a Polygon needs at least an exterior ring".into())
+    }));
+    let exterior = try!(positions_to_line_string(exterior_ring, 4, "ring"));
+    let interiors = try!(rings[1..].iter()
+        .map(|ring| positions_to_line_string(ring, 4, "ring"))
+        .collect::<Result<Vec<_>, _>>());
+    Ok(::geo_types::Polygon::new(exterior, interiors))
+}
+
+impl<'a> TryFrom<&'a Value> for ::geo_types::Point<f64> {
+    type Error = Error;
+
+    fn try_from(value: &'a Value) -> Result<Self, Self::Error> {
+        match *value {
+            Value::Point(ref position) => {
+                let coord = try!(position_to_coordinate(position));
+                Ok(::geo_types::Point::new(coord.x, coord.y))
+            }
+            _ => Err(Error::InvalidGeometryConversion(
+                format!("expected a Point, found a {}", value.type_name()))),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Value> for ::geo_types::MultiPoint<f64> {
+    type Error = Error;
+
+    fn try_from(value: &'a Value) -> Result<Self, Self::Error> {
+        match *value {
+            Value::MultiPoint(ref positions) => {
+                let points = try!(positions.iter()
+                    .map(|p| position_to_coordinate(p).map(|c| ::geo_types::Point::new(c.x, c.y)))
+                    .collect::<Result<Vec<_>, _>>());
+                Ok(::geo_types::MultiPoint(points))
+            }
+            _ => Err(Error::InvalidGeometryConversion(
+                format!("expected a MultiPoint, found a {}", value.type_name()))),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Value> for ::geo_types::LineString<f64> {
+    type Error = Error;
+
+    fn try_from(value: &'a Value) -> Result<Self, Self::Error> {
+        match *value {
+            Value::LineString(ref positions) => positions_to_line_string(positions, 2, "LineString"),
+            _ => Err(Error::InvalidGeometryConversion(
+                format!("expected a LineString, found a {}", value.type_name()))),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Value> for ::geo_types::MultiLineString<f64> {
+    type Error = Error;
+
+    fn try_from(value: &'a Value) -> Result<Self, Self::Error> {
+        match *value {
+            Value::MultiLineString(ref lines) => {
+                let lines = try!(lines.iter()
+                    .map(|line| positions_to_line_string(line, 2, "LineString"))
+                    .collect::<Result<Vec<_>, _>>());
+                Ok(::geo_types::MultiLineString(lines))
+            }
+            _ => Err(Error::InvalidGeometryConversion(
+                format!("expected a MultiLineString, found a {}", value.type_name()))),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Value> for ::geo_types::Polygon<f64> {
+    type Error = Error;
+
+    fn try_from(value: &'a Value) -> Result<Self, Self::Error> {
+        match *value {
+            Value::Polygon(ref rings) => rings_to_polygon(rings),
+            _ => Err(Error::InvalidGeometryConversion(
+                format!("expected a Polygon, found a {}", value.type_name()))),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Value> for ::geo_types::MultiPolygon<f64> {
+    type Error = Error;
+
+    fn try_from(value: &'a Value) -> Result<Self, Self::Error> {
+        match *value {
+            Value::MultiPolygon(ref polygons) => {
+                let polygons = try!(polygons.iter().map(rings_to_polygon).collect::<Result<Vec<_>, _>>());
+                Ok(::geo_types::MultiPolygon(polygons))
+            }
+            _ => Err(Error::InvalidGeometryConversion(
+                format!("expected a MultiPolygon, found a {}", value.type_name()))),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Value> for ::geo_types::GeometryCollection<f64> {
+    type Error = Error;
+
+    fn try_from(value: &'a Value) -> Result<Self, Self::Error> {
+        match *value {
+            Value::GeometryCollection(ref geometries) => {
+                let geometries = try!(geometries.iter()
+                    .map(::geo_types::Geometry::try_from)
+                    .collect::<Result<Vec<_>, _>>());
+                Ok(::geo_types::GeometryCollection(geometries))
+            }
+            _ => Err(Error::InvalidGeometryConversion(
+                format!("expected a GeometryCollection, found a {}", value.type_name()))),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Geometry> for ::geo_types::Geometry<f64> {
+    type Error = Error;
+
+    fn try_from(geometry: &'a Geometry) -> Result<Self, Self::Error> {
+        match geometry.value {
+            Value::Point(..) =>
+                Ok(::geo_types::Geometry::Point(try!(TryFrom::try_from(&geometry.value)))),
+            Value::MultiPoint(..) =>
+                Ok(::geo_types::Geometry::MultiPoint(try!(TryFrom::try_from(&geometry.value)))),
+            Value::LineString(..) =>
+                Ok(::geo_types::Geometry::LineString(try!(TryFrom::try_from(&geometry.value)))),
+            Value::MultiLineString(..) =>
+                Ok(::geo_types::Geometry::MultiLineString(try!(TryFrom::try_from(&geometry.value)))),
+            Value::Polygon(..) =>
+                Ok(::geo_types::Geometry::Polygon(try!(TryFrom::try_from(&geometry.value)))),
+            Value::MultiPolygon(..) =>
+                Ok(::geo_types::Geometry::MultiPolygon(try!(TryFrom::try_from(&geometry.value)))),
+            Value::GeometryCollection(..) =>
+                Ok(::geo_types::Geometry::GeometryCollection(try!(TryFrom::try_from(&geometry.value)))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ::geometry::Value;
+
+    #[test]
+    fn try_from_point_rejects_non_point_value() {
+        let line_string = Value::LineString(vec![[0.0, 0.0].into(), [1.0, 1.0].into()]);
+        let result: Result<::geo_types::Point<f64>, _> = TryFrom::try_from(&line_string);
+        match result.unwrap_err() {
+            ::Error::InvalidGeometryConversion(ref reason) => assert!(reason.contains("LineString")),
+            other => panic!("expected InvalidGeometryConversion, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn try_from_polygon_rejects_ring_with_too_few_positions() {
+        let polygon = Value::Polygon(vec![
+            vec![[0.0, 0.0].into(), [1.0, 0.0].into(), [1.0, 1.0].into()],
+        ]);
+        let result: Result<::geo_types::Polygon<f64>, _> = TryFrom::try_from(&polygon);
+        match result.unwrap_err() {
+            ::Error::InvalidGeometryConversion(..) => {}
+            other => panic!("expected InvalidGeometryConversion, got {:?}", other),
+        }
+    }
+}
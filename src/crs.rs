@@ -0,0 +1,64 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// A GeoJSON `crs` member.
+///
+/// [GeoJSON Format Specification § 3]
+/// (http://geojson.org/geojson-spec.html#coordinate-reference-system-objects)
+#[derive(Clone, Debug, PartialEq)]
+pub enum Crs {
+    /// A CRS identified by name, e.g. `"urn:ogc:def:crs:OGC:1.3:CRS84"`.
+    Named {
+        name: String,
+    },
+    /// A CRS identified by a dereferenceable URI.
+    Linked {
+        href: String,
+        type_: Option<String>,
+    },
+}
+
+/// URN/name forms that the GeoJSON and OGC CRS ecosystems use for longitude/latitude WGS84,
+/// i.e. EPSG:4326 with axis order swapped to match GeoJSON's `[lon, lat]` position order.
+const WGS84_NAMES: &'static [&'static str] = &[
+    "urn:ogc:def:crs:OGC:1.3:CRS84",
+    "urn:ogc:def:crs:OGC::CRS84",
+    "EPSG:4326",
+    "urn:ogc:def:crs:EPSG::4326",
+    "urn:ogc:def:crs:EPSG:4326",
+];
+
+impl Crs {
+    /// Returns `true` if this CRS resolves to longitude/latitude WGS84 (`CRS84`/`EPSG:4326`),
+    /// the coordinate reference system GeoJSON assumes when `crs` is absent.
+    ///
+    /// A `Crs::Linked` href is never dereferenced — this crate makes no network or filesystem
+    /// calls — so a linked CRS always reports `false` here even if the href it points at
+    /// happens to describe WGS84. `GeometryParser::require_wgs84` still surfaces that href in
+    /// `Error::InvalidCrs { found }` so callers can make that judgment call themselves.
+    pub fn is_wgs84(&self) -> bool {
+        match *self {
+            Crs::Named { ref name } => WGS84_NAMES.contains(&name.as_str()),
+            Crs::Linked { .. } => false,
+        }
+    }
+
+    /// A human-readable description of this CRS, used in `Error::InvalidCrs` messages.
+    pub fn describe(&self) -> String {
+        match *self {
+            Crs::Named { ref name } => name.clone(),
+            Crs::Linked { ref href, .. } => href.clone(),
+        }
+    }
+}
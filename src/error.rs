@@ -0,0 +1,68 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error::Error as StdError;
+use std::fmt;
+
+/// Everything that can go wrong while reading or writing GeoJSON with this crate.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    /// A `Geometry`'s `type` member didn't match one of the seven GeoJSON geometry types.
+    GeometryUnknownType,
+
+    /// A `Value` couldn't be converted into the requested `geo-types` geometry (e.g. a ring
+    /// with fewer than four positions, or a `Value` of a different variant than the one
+    /// asked for), or a geometry's members were malformed while being read from JSON.
+    InvalidGeometryConversion(String),
+
+    /// `GeometryParser::require_wgs84` was enabled and the parsed `crs` member named or
+    /// linked a different coordinate reference system.
+    InvalidCrs {
+        /// The name or href of the CRS that was found, if any.
+        found: Option<String>,
+    },
+
+    /// `GeometryParser::validate_coordinate_bounds` was enabled and a position's longitude
+    /// or latitude fell outside `[-180, 180]`/`[-90, 90]`.
+    CoordinateOutOfRange {
+        x: f64,
+        y: f64,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::GeometryUnknownType =>
+                write!(f, "encountered unknown Geometry type"),
+            Error::InvalidGeometryConversion(ref reason) =>
+                write!(f, "could not convert Value into a geo-types geometry: {}", reason),
+            Error::InvalidCrs { ref found } =>
+                write!(f, "expected CRS to resolve to EPSG:4326 (WGS84), found {:?}", found),
+            Error::CoordinateOutOfRange { x, y } =>
+                write!(f, "position ({}, {}) is out of range for longitude/latitude WGS84", x, y),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::GeometryUnknownType => "encountered unknown Geometry type",
+            Error::InvalidGeometryConversion(..) => "could not convert Value into a geo-types geometry",
+            Error::InvalidCrs { .. } => "CRS did not resolve to EPSG:4326 (WGS84)",
+            Error::CoordinateOutOfRange { .. } => "coordinate is out of range for longitude/latitude WGS84",
+        }
+    }
+}
@@ -21,109 +21,122 @@ use ::json::{Serialize, Deserialize, Serializer, Deserializer, SerdeError};
 
 use ::json::{JsonValue, JsonObject, json_val};
 
-use ::{Bbox, Crs, Error, LineStringType, PointType, PolygonType, FromObject, util};
+use ::{Bbox, Crs, Error, FromObject, util};
+use ::position::{CoordFloat, PointTypeBase};
 
 
-/// The underlying Geometry value
+/// The underlying Geometry value, generic over its coordinate type `T`.
+///
+/// `Value` is an alias for `ValueBase<f64>`, so existing code that only ever dealt with
+/// 64-bit coordinates keeps compiling unmodified. Pick a smaller `T` (e.g. `f32`) to halve
+/// the memory footprint of large `FeatureCollection`s at the cost of precision.
 #[derive(Clone, Debug, PartialEq)]
-pub enum Value {
+pub enum ValueBase<T: CoordFloat> {
     /// Point
     ///
     /// [GeoJSON Format Specification § 2.1.2]
     /// (http://geojson.org/geojson-spec.html#point)
-    Point(PointType),
+    Point(PointTypeBase<T>),
 
     /// MultiPoint
     ///
     /// [GeoJSON Format Specification § 2.1.3]
     /// (http://geojson.org/geojson-spec.html#multipoint)
-    MultiPoint(Vec<PointType>),
+    MultiPoint(Vec<PointTypeBase<T>>),
 
     /// LineString
     ///
     /// [GeoJSON Format Specification § 2.1.4]
     /// (http://geojson.org/geojson-spec.html#linestring)
-    LineString(LineStringType),
+    LineString(LineStringTypeBase<T>),
 
     /// MultiLineString
     ///
     /// [GeoJSON Format Specification § 2.1.5]
     /// (http://geojson.org/geojson-spec.html#multilinestring)
-    MultiLineString(Vec<LineStringType>),
+    MultiLineString(Vec<LineStringTypeBase<T>>),
 
     /// Polygon
     ///
     /// [GeoJSON Format Specification § 2.1.6]
     /// (http://geojson.org/geojson-spec.html#polygon)
-    Polygon(PolygonType),
+    Polygon(PolygonTypeBase<T>),
 
     /// MultiPolygon
     ///
     /// [GeoJSON Format Specification § 2.1.7]
     /// (http://geojson.org/geojson-spec.html#multipolygon)
-    MultiPolygon(Vec<PolygonType>),
+    MultiPolygon(Vec<PolygonTypeBase<T>>),
 
     /// GeometryCollection
     ///
     /// [GeoJSON Format Specification § 2.1.8]
     /// (http://geojson.org/geojson-spec.html#geometry-collection)
-    GeometryCollection(Vec<Geometry>),
+    GeometryCollection(Vec<GeometryBase<T>>),
 }
 
+/// The underlying Geometry value, hard-coded to 64-bit coordinates. See
+/// [`ValueBase`](enum.ValueBase.html) for a version generic over the coordinate type.
+pub type Value = ValueBase<f64>;
+
 #[cfg(not(feature = "with-serde"))]
-impl ToJson for Value {
+impl<T: CoordFloat + ::rustc_serialize::Encodable> ToJson for ValueBase<T> {
     fn to_json(&self) -> JsonValue {
         return match *self {
-            Value::Point(ref x) => json_val(x),
-            Value::MultiPoint(ref x) => json_val(x),
-            Value::LineString(ref x) => json_val(x),
-            Value::MultiLineString(ref x) => json_val(x),
-            Value::Polygon(ref x) => json_val(x),
-            Value::MultiPolygon(ref x) => json_val(x),
-            Value::GeometryCollection(ref x) => json_val(x),
+            ValueBase::Point(ref x) => json_val(x),
+            ValueBase::MultiPoint(ref x) => json_val(x),
+            ValueBase::LineString(ref x) => json_val(x),
+            ValueBase::MultiLineString(ref x) => json_val(x),
+            ValueBase::Polygon(ref x) => json_val(x),
+            ValueBase::MultiPolygon(ref x) => json_val(x),
+            ValueBase::GeometryCollection(ref x) => json_val(x),
         };
     }
 }
 
 #[cfg(feature = "with-serde")]
-impl<'a> From<&'a Value> for JsonValue {
-    fn from(value: &'a Value) -> JsonValue {
+impl<'a, T: CoordFloat + Serialize> From<&'a ValueBase<T>> for JsonValue {
+    fn from(value: &'a ValueBase<T>) -> JsonValue {
         return match *value {
-            Value::Point(ref x) => json_val(x),
-            Value::MultiPoint(ref x) => json_val(x),
-            Value::LineString(ref x) => json_val(x),
-            Value::MultiLineString(ref x) => json_val(x),
-            Value::Polygon(ref x) => json_val(x),
-            Value::MultiPolygon(ref x) => json_val(x),
-            Value::GeometryCollection(ref x) => json_val(x),
+            ValueBase::Point(ref x) => json_val(x),
+            ValueBase::MultiPoint(ref x) => json_val(x),
+            ValueBase::LineString(ref x) => json_val(x),
+            ValueBase::MultiLineString(ref x) => json_val(x),
+            ValueBase::Polygon(ref x) => json_val(x),
+            ValueBase::MultiPolygon(ref x) => json_val(x),
+            ValueBase::GeometryCollection(ref x) => json_val(x),
         };
     }
 }
 
 #[cfg(feature = "with-serde")]
-impl Serialize for Value {
+impl<T: CoordFloat + Serialize> Serialize for ValueBase<T> {
     fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
     where S: Serializer {
         JsonValue::from(self).serialize(serializer)
     }
 }
 
-/// Geometry Objects
+/// Geometry Objects, generic over the coordinate type `T`.
 ///
 /// [GeoJSON Format Specification § 2.1]
 /// (http://geojson.org/geojson-spec.html#geometry-objects)
 #[derive(Clone, Debug, PartialEq)]
-pub struct Geometry {
+pub struct GeometryBase<T: CoordFloat> {
     pub bbox: Option<Bbox>,
-    pub value: Value,
+    pub value: ValueBase<T>,
     pub crs: Option<Crs>,
 }
 
-impl Geometry {
-    /// Returns a new `Geometry` with the specified `value`. `bbox` and `crs` will be set to
+/// Geometry Objects, hard-coded to 64-bit coordinates. See
+/// [`GeometryBase`](struct.GeometryBase.html) for a version generic over the coordinate type.
+pub type Geometry = GeometryBase<f64>;
+
+impl<T: CoordFloat> GeometryBase<T> {
+    /// Returns a new `GeometryBase` with the specified `value`. `bbox` and `crs` will be set to
     /// `None`.
-    pub fn new(value: Value) -> Self {
-        Geometry {
+    pub fn new(value: ValueBase<T>) -> Self {
+        GeometryBase {
             bbox: None,
             value: value,
             crs: None,
@@ -131,61 +144,74 @@ impl Geometry {
     }
 }
 
-impl<'a> From<&'a Geometry> for JsonObject {
-    fn from(geometry: &'a Geometry) -> JsonObject {
-        let mut map = BTreeMap::new();
-        if let Some(ref crs) = geometry.crs {
-            map.insert(String::from("crs"), json_val(crs));
-        }
-        if let Some(ref bbox) = geometry.bbox {
-            map.insert(String::from("bbox"), json_val(bbox));
-        }
-
-        let ty = String::from(match geometry.value {
-            Value::Point(..) => "Point",
-            Value::MultiPoint(..) => "MultiPoint",
-            Value::LineString(..) => "LineString",
-            Value::MultiLineString(..) => "MultiLineString",
-            Value::Polygon(..) => "Polygon",
-            Value::MultiPolygon(..) => "MultiPolygon",
-            Value::GeometryCollection(..) => "GeometryCollection",
-        });
+#[cfg(feature = "with-serde")]
+impl<'a, T: CoordFloat + Serialize> From<&'a GeometryBase<T>> for JsonObject {
+    fn from(geometry: &'a GeometryBase<T>) -> JsonObject {
+        json_object_from_geometry(geometry)
+    }
+}
 
-        map.insert(String::from("type"), json_val(&ty));
+#[cfg(not(feature = "with-serde"))]
+impl<'a, T: CoordFloat + ::rustc_serialize::Encodable> From<&'a GeometryBase<T>> for JsonObject {
+    fn from(geometry: &'a GeometryBase<T>) -> JsonObject {
+        json_object_from_geometry(geometry)
+    }
+}
 
-        map.insert(String::from(match geometry.value {
-            Value::GeometryCollection(..) => "geometries",
-            _ => "coordinates",
-        }), json_val(&geometry.value));
-        return map;
+fn json_object_from_geometry<T: CoordFloat>(geometry: &GeometryBase<T>) -> JsonObject
+    where for<'a> JsonValue: From<&'a ValueBase<T>> {
+    let mut map = BTreeMap::new();
+    if let Some(ref crs) = geometry.crs {
+        map.insert(String::from("crs"), json_val(crs));
+    }
+    if let Some(ref bbox) = geometry.bbox {
+        map.insert(String::from("bbox"), json_val(bbox));
     }
+
+    let ty = String::from(match geometry.value {
+        ValueBase::Point(..) => "Point",
+        ValueBase::MultiPoint(..) => "MultiPoint",
+        ValueBase::LineString(..) => "LineString",
+        ValueBase::MultiLineString(..) => "MultiLineString",
+        ValueBase::Polygon(..) => "Polygon",
+        ValueBase::MultiPolygon(..) => "MultiPolygon",
+        ValueBase::GeometryCollection(..) => "GeometryCollection",
+    });
+
+    map.insert(String::from("type"), json_val(&ty));
+
+    map.insert(String::from(match geometry.value {
+        ValueBase::GeometryCollection(..) => "geometries",
+        _ => "coordinates",
+    }), JsonValue::from(&geometry.value));
+    return map;
 }
 
-impl FromObject for Geometry {
+impl<T: CoordFloat> FromObject for GeometryBase<T> {
     fn from_object(object: &JsonObject) -> Result<Self, Error> {
         let type_ = expect_type!(object);
         let value = match type_ {
             "Point" =>
-                Value::Point(try!(util::get_coords_one_pos(object))),
+                ValueBase::Point(try!(util::get_coords_one_pos(object))),
             "MultiPoint" =>
-                Value::MultiPoint(try!(util::get_coords_1d_pos(object))),
+                ValueBase::MultiPoint(try!(util::get_coords_1d_pos(object))),
             "LineString" =>
-                Value::LineString(try!(util::get_coords_1d_pos(object))),
+                ValueBase::LineString(try!(util::get_coords_1d_pos(object))),
             "MultiLineString" =>
-                Value::MultiLineString(try!(util::get_coords_2d_pos(object))),
+                ValueBase::MultiLineString(try!(util::get_coords_2d_pos(object))),
             "Polygon" =>
-                Value::Polygon(try!(util::get_coords_2d_pos(object))),
+                ValueBase::Polygon(try!(util::get_coords_2d_pos(object))),
             "MultiPolygon" =>
-                Value::MultiPolygon(try!(util::get_coords_3d_pos(object))),
+                ValueBase::MultiPolygon(try!(util::get_coords_3d_pos(object))),
             "GeometryCollection" =>
-                Value::GeometryCollection(try!(util::get_geometries(object))),
+                ValueBase::GeometryCollection(try!(util::get_geometries(object))),
             _ => return Err(Error::GeometryUnknownType),
         };
 
         let bbox = try!(util::get_bbox(object));
         let crs = try!(util::get_crs(object));
 
-        return Ok(Geometry {
+        return Ok(GeometryBase {
             bbox: bbox,
             value: value,
             crs: crs,
@@ -194,30 +220,35 @@ impl FromObject for Geometry {
 }
 
 #[cfg(not(feature = "with-serde"))]
-impl ToJson for Geometry {
+impl<T: CoordFloat + ::rustc_serialize::Encodable> ToJson for GeometryBase<T> {
     fn to_json(&self) -> JsonValue {
         return ::rustc_serialize::json::Json::Object(self.into());
     }
 }
 
 #[cfg(feature = "with-serde")]
-impl Serialize for Geometry {
+impl<T: CoordFloat + Serialize> Serialize for GeometryBase<T> {
     fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
     where S: Serializer {
         JsonObject::from(self).serialize(serializer)
     }
 }
 
+// Deserialization reads every GeoJSON number as `f64` off the wire (via `JsonValue`) and
+// then narrows it to `T` inside `util`'s coordinate readers, which `GeometryBase::from_object`
+// calls; narrowing failures surface as `Error` the same way any other malformed input would.
+// Bounding this `impl` on `T: CoordFloat` (rather than hard-coding `Geometry` = `GeometryBase<f64>`)
+// is what makes `GeometryBase<f32>` deserializable, not just constructible and serializable.
 #[cfg(feature = "with-serde")]
-impl Deserialize for Geometry {
-    fn deserialize<D>(deserializer: &mut D) -> Result<Geometry, D::Error>
+impl<T: CoordFloat> Deserialize for GeometryBase<T> {
+    fn deserialize<D>(deserializer: &mut D) -> Result<GeometryBase<T>, D::Error>
     where D: Deserializer {
         use std::error::Error as StdError;
 
         let val = try!(JsonValue::deserialize(deserializer));
 
         if let Some(geo) = val.as_object() {
-            Geometry::from_object(geo).map_err(|e| D::Error::custom(e.description()))
+            GeometryBase::from_object(geo).map_err(|e| D::Error::custom(e.description()))
         }
         else {
             Err(D::Error::custom("expected json object"))
@@ -256,7 +287,7 @@ mod tests {
     fn encode_decode_geometry() {
         let geometry_json_str = "{\"coordinates\":[1.1,2.1],\"type\":\"Point\"}";
         let geometry = Geometry {
-            value: Value::Point(vec![1.1, 2.1]),
+            value: Value::Point([1.1, 2.1].into()),
             crs: None,
             bbox: None,
         };
@@ -272,4 +303,15 @@ mod tests {
         };
         assert_eq!(decoded_geometry, geometry);
     }
+
+    #[test]
+    fn geometry_base_is_generic_over_coordinate_type() {
+        use ::geometry::{GeometryBase, ValueBase};
+
+        let geometry: GeometryBase<f32> = GeometryBase::new(ValueBase::Point([1.1, 2.1].into()));
+        match geometry.value {
+            ValueBase::Point(ref p) => assert_eq!(&**p, &[1.1f32, 2.1f32][..]),
+            _ => unreachable!(),
+        }
+    }
 }
@@ -0,0 +1,112 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ::geometry::Geometry;
+use ::json::JsonObject;
+use ::processor::GeomProcessor;
+use ::{Error, FromObject};
+
+/// A configurable `Geometry` parser.
+///
+/// By default, `GeometryParser` behaves exactly like `Geometry::from_object`: any `crs`
+/// member is accepted verbatim and coordinates aren't range-checked. Opt into
+/// [`require_wgs84`](#method.require_wgs84) and/or
+/// [`validate_coordinate_bounds`](#method.validate_coordinate_bounds) for RFC 7946-style
+/// strictness, e.g. to reject data whose `crs` isn't longitude/latitude WGS84 or that looks
+/// like it has swapped latitude and longitude.
+#[derive(Clone, Debug, Default)]
+pub struct GeometryParser {
+    require_wgs84: bool,
+    validate_coordinate_bounds: bool,
+}
+
+impl GeometryParser {
+    /// Creates a parser with the crate's historical, permissive defaults.
+    pub fn new() -> Self {
+        GeometryParser {
+            require_wgs84: false,
+            validate_coordinate_bounds: false,
+        }
+    }
+
+    /// When `true`, reject any `Geometry` whose `crs` doesn't resolve to longitude/latitude
+    /// WGS84 (`CRS84`/`EPSG:4326`). A missing `crs` member still passes, since GeoJSON
+    /// defaults to WGS84 when `crs` is absent.
+    pub fn require_wgs84(mut self, require: bool) -> Self {
+        self.require_wgs84 = require;
+        self
+    }
+
+    /// When `true`, reject any `Geometry` with a position whose first two components fall
+    /// outside `[-180, 180]`/`[-90, 90]`. This catches swapped latitude/longitude input, but
+    /// will also reject projected (non-degree) coordinates, so leave it off unless you know
+    /// the input claims to be in degrees.
+    pub fn validate_coordinate_bounds(mut self, validate: bool) -> Self {
+        self.validate_coordinate_bounds = validate;
+        self
+    }
+
+    /// Parses `object` into a `Geometry`, applying whichever checks this parser was
+    /// configured with.
+    pub fn parse(&self, object: &JsonObject) -> Result<Geometry, Error> {
+        let geometry = try!(Geometry::from_object(object));
+
+        if self.require_wgs84 {
+            if let Some(ref crs) = geometry.crs {
+                if !crs.is_wgs84() {
+                    return Err(Error::InvalidCrs { found: Some(crs.describe()) });
+                }
+            }
+        }
+
+        if self.validate_coordinate_bounds {
+            let mut validator = BoundsValidator { out_of_range: None };
+            try!(geometry.process(&mut validator));
+            if let Some((x, y)) = validator.out_of_range {
+                return Err(Error::CoordinateOutOfRange { x: x, y: y });
+            }
+        }
+
+        Ok(geometry)
+    }
+}
+
+/// Reuses the `GeomProcessor` visitor to check every coordinate against `[-180, 180]` /
+/// `[-90, 90]` without cloning the geometry into another representation first.
+struct BoundsValidator {
+    out_of_range: Option<(f64, f64)>,
+}
+
+impl GeomProcessor for BoundsValidator {
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<(), Error> {
+        if self.out_of_range.is_none() && (x < -180.0 || x > 180.0 || y < -90.0 || y > 90.0) {
+            self.out_of_range = Some((x, y));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::geometry::{GeometryBase, ValueBase};
+    use super::BoundsValidator;
+
+    #[test]
+    fn bounds_validator_flags_out_of_range_position() {
+        let geometry = GeometryBase::new(ValueBase::Point([200.0, 10.0].into()));
+        let mut validator = BoundsValidator { out_of_range: None };
+        geometry.process(&mut validator).unwrap();
+        assert_eq!(validator.out_of_range, Some((200.0, 10.0)));
+    }
+}
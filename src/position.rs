@@ -0,0 +1,147 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::ops::Deref;
+
+use ::num_traits::Float;
+use ::tinyvec::TinyVec;
+
+#[cfg(not(feature = "with-serde"))]
+use ::json::ToJson;
+#[cfg(feature = "with-serde")]
+use ::json::{Serialize, Serializer};
+
+use ::json::JsonValue;
+
+/// A bound on the numeric type used to store coordinates in a [`PositionBase`]
+/// (struct.PositionBase.html), generic `Value`, or `Geometry`. Implemented automatically for
+/// every `Float + Copy + Debug + Default` type, e.g. `f32` and `f64`; a custom fixed-point
+/// type gets it by implementing those traits.
+pub trait CoordFloat: Float + Copy + Debug + Default {}
+
+impl<T: Float + Copy + Debug + Default> CoordFloat for T {}
+
+/// The coordinates of a single GeoJSON position (`[x, y]`, `[x, y, z]`, or higher), generic
+/// over the coordinate's numeric representation.
+///
+/// The overwhelming majority of real-world positions are 2D or 3D, so the first four
+/// coordinates are kept inline on the stack; a position needs more than that only in the
+/// rare higher-dimension case, at which point it spills onto the heap like a normal `Vec`.
+/// `Position` is an alias for `PositionBase<f64>`, matching the default used everywhere else
+/// in the crate.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PositionBase<T: CoordFloat>(TinyVec<[T; 4]>);
+
+/// The coordinates of a single GeoJSON position, hard-coded to 64-bit coordinates. See
+/// [`PositionBase`](struct.PositionBase.html) for a version generic over the coordinate type.
+pub type Position = PositionBase<f64>;
+
+impl<T: CoordFloat> Deref for PositionBase<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T: CoordFloat> PositionBase<T> {
+    /// An empty position, ready to be filled one coordinate at a time with
+    /// [`push`](#method.push). Prefer this over collecting into a `Vec` first when the
+    /// coordinates come from something that can only be read one at a time, e.g. a JSON
+    /// number sequence: it lets the common 2D/3D case stay inline on the stack instead of
+    /// allocating an intermediate `Vec` that's immediately thrown away.
+    pub fn new() -> Self {
+        PositionBase(TinyVec::new())
+    }
+
+    /// Appends a single coordinate, spilling onto the heap once more than four are pushed.
+    pub fn push(&mut self, value: T) {
+        self.0.push(value);
+    }
+}
+
+impl<T: CoordFloat> From<[T; 2]> for PositionBase<T> {
+    fn from(coords: [T; 2]) -> Self {
+        PositionBase(coords.iter().cloned().collect())
+    }
+}
+
+impl<T: CoordFloat> From<[T; 3]> for PositionBase<T> {
+    fn from(coords: [T; 3]) -> Self {
+        PositionBase(coords.iter().cloned().collect())
+    }
+}
+
+impl<T: CoordFloat> From<Vec<T>> for PositionBase<T> {
+    fn from(coords: Vec<T>) -> Self {
+        PositionBase(coords.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "with-serde")]
+impl<T: CoordFloat + Serialize> Serialize for PositionBase<T> {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+    where S: Serializer {
+        self.0.as_slice().serialize(serializer)
+    }
+}
+
+#[cfg(not(feature = "with-serde"))]
+impl<T: CoordFloat + ::rustc_serialize::Encodable> ToJson for PositionBase<T> {
+    fn to_json(&self) -> JsonValue {
+        self.0.as_slice().to_json()
+    }
+}
+
+/// The type of a single position's coordinates, generic over the coordinate's numeric
+/// representation. `PointType` is an alias for `PointTypeBase<f64>`, matching the default
+/// used everywhere else in the crate.
+pub type PointTypeBase<T> = PositionBase<T>;
+pub type PointType = PointTypeBase<f64>;
+
+/// The coordinates of a `LineString`, generic over the coordinate's numeric representation.
+pub type LineStringTypeBase<T> = Vec<PointTypeBase<T>>;
+pub type LineStringType = LineStringTypeBase<f64>;
+
+/// The coordinates of a `Polygon` (a list of rings), generic over the coordinate's numeric
+/// representation.
+pub type PolygonTypeBase<T> = Vec<LineStringTypeBase<T>>;
+pub type PolygonType = PolygonTypeBase<f64>;
+
+#[cfg(test)]
+mod tests {
+    use super::Position;
+
+    #[test]
+    fn position_from_array_derefs_to_slice() {
+        let pos = Position::from([1.1, 2.1]);
+        assert_eq!(&*pos, &[1.1, 2.1][..]);
+    }
+
+    #[test]
+    fn position_from_vec_spills_to_heap_past_four_coords() {
+        let pos = Position::from(vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert!(pos.0.is_heap());
+        assert_eq!(&*pos, &[1.0, 2.0, 3.0, 4.0, 5.0][..]);
+    }
+
+    #[test]
+    fn position_new_fills_via_push() {
+        let mut pos = Position::new();
+        pos.push(1.1);
+        pos.push(2.1);
+        assert_eq!(&*pos, &[1.1, 2.1][..]);
+    }
+}
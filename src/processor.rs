@@ -0,0 +1,335 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ::geometry::{GeometryBase, ValueBase};
+use ::position::{CoordFloat, PointTypeBase};
+use ::Error;
+
+/// A visitor over the coordinates of a `Geometry`, driven in document order by
+/// [`GeometryBase::process`](struct.GeometryBase.html#method.process).
+///
+/// Implementing just `xy` (and the `_begin`/`_end` pairs you care about) lets callers
+/// transcode GeoJSON straight into another geometry backend, or compute things like a
+/// bounding box, without `Value` ever materializing the full nested `Vec` tree for the
+/// target representation. All methods have a no-op default so implementors only override
+/// the callbacks they need.
+pub trait GeomProcessor {
+    /// A single coordinate pair, widened to `f64`. `idx` is this coordinate's position
+    /// within the enclosing point/linestring/ring.
+    fn xy(&mut self, _x: f64, _y: f64, _idx: usize) -> Result<(), Error> { Ok(()) }
+
+    fn point_begin(&mut self, _idx: usize) -> Result<(), Error> { Ok(()) }
+    fn point_end(&mut self, _idx: usize) -> Result<(), Error> { Ok(()) }
+
+    fn multipoint_begin(&mut self, _size: usize, _idx: usize) -> Result<(), Error> { Ok(()) }
+    fn multipoint_end(&mut self, _idx: usize) -> Result<(), Error> { Ok(()) }
+
+    fn linestring_begin(&mut self, _size: usize, _idx: usize) -> Result<(), Error> { Ok(()) }
+    fn linestring_end(&mut self, _idx: usize) -> Result<(), Error> { Ok(()) }
+
+    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<(), Error> { Ok(()) }
+    fn multilinestring_end(&mut self, _idx: usize) -> Result<(), Error> { Ok(()) }
+
+    fn polygon_begin(&mut self, _size: usize, _idx: usize) -> Result<(), Error> { Ok(()) }
+    fn polygon_end(&mut self, _idx: usize) -> Result<(), Error> { Ok(()) }
+
+    fn multipolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<(), Error> { Ok(()) }
+    fn multipolygon_end(&mut self, _idx: usize) -> Result<(), Error> { Ok(()) }
+
+    fn geometry_collection_begin(&mut self, _size: usize, _idx: usize) -> Result<(), Error> { Ok(()) }
+    fn geometry_collection_end(&mut self, _idx: usize) -> Result<(), Error> { Ok(()) }
+}
+
+impl<T: CoordFloat> GeometryBase<T> {
+    /// Walks this geometry's coordinates in document order, driving `processor`'s
+    /// callbacks. This never clones a coordinate into an intermediate `Vec`: each
+    /// position is read directly out of `self` and handed to `processor.xy` as `f64`.
+    pub fn process<P: GeomProcessor>(&self, processor: &mut P) -> Result<(), Error> {
+        process_value(&self.value, processor, 0)
+    }
+}
+
+fn process_position<T: CoordFloat, P: GeomProcessor>(
+    pos: &PointTypeBase<T>, processor: &mut P, idx: usize,
+) -> Result<(), Error> {
+    if pos.len() < 2 {
+        return Err(Error::InvalidGeometryConversion(
+            "a position needs at least 2 coordinates".into()));
+    }
+    // `to_f64` can return `None` for a custom `CoordFloat` (e.g. a fixed-point type) even
+    // though it never does for `f32`/`f64`, so narrowing failure is reported as an `Error`
+    // rather than assumed away.
+    let x = try!(pos[0].to_f64().ok_or_else(|| Error::InvalidGeometryConversion(
+        "coordinate does not fit in f64".into())));
+    let y = try!(pos[1].to_f64().ok_or_else(|| Error::InvalidGeometryConversion(
+        "coordinate does not fit in f64".into())));
+    try!(processor.xy(x, y, idx));
+    Ok(())
+}
+
+fn process_value<T: CoordFloat, P: GeomProcessor>(
+    value: &ValueBase<T>, processor: &mut P, idx: usize,
+) -> Result<(), Error> {
+    match *value {
+        ValueBase::Point(ref pos) => {
+            try!(processor.point_begin(idx));
+            try!(process_position(pos, processor, 0));
+            try!(processor.point_end(idx));
+        }
+        ValueBase::MultiPoint(ref points) => {
+            try!(processor.multipoint_begin(points.len(), idx));
+            for (i, pos) in points.iter().enumerate() {
+                try!(process_position(pos, processor, i));
+            }
+            try!(processor.multipoint_end(idx));
+        }
+        ValueBase::LineString(ref line) => {
+            try!(processor.linestring_begin(line.len(), idx));
+            for (i, pos) in line.iter().enumerate() {
+                try!(process_position(pos, processor, i));
+            }
+            try!(processor.linestring_end(idx));
+        }
+        ValueBase::MultiLineString(ref lines) => {
+            try!(processor.multilinestring_begin(lines.len(), idx));
+            for (i, line) in lines.iter().enumerate() {
+                try!(processor.linestring_begin(line.len(), i));
+                for (j, pos) in line.iter().enumerate() {
+                    try!(process_position(pos, processor, j));
+                }
+                try!(processor.linestring_end(i));
+            }
+            try!(processor.multilinestring_end(idx));
+        }
+        ValueBase::Polygon(ref rings) => {
+            try!(processor.polygon_begin(rings.len(), idx));
+            for (i, ring) in rings.iter().enumerate() {
+                try!(processor.linestring_begin(ring.len(), i));
+                for (j, pos) in ring.iter().enumerate() {
+                    try!(process_position(pos, processor, j));
+                }
+                try!(processor.linestring_end(i));
+            }
+            try!(processor.polygon_end(idx));
+        }
+        ValueBase::MultiPolygon(ref polygons) => {
+            try!(processor.multipolygon_begin(polygons.len(), idx));
+            for (i, rings) in polygons.iter().enumerate() {
+                try!(processor.polygon_begin(rings.len(), i));
+                for (j, ring) in rings.iter().enumerate() {
+                    try!(processor.linestring_begin(ring.len(), j));
+                    for (k, pos) in ring.iter().enumerate() {
+                        try!(process_position(pos, processor, k));
+                    }
+                    try!(processor.linestring_end(j));
+                }
+                try!(processor.polygon_end(i));
+            }
+            try!(processor.multipolygon_end(idx));
+        }
+        ValueBase::GeometryCollection(ref geometries) => {
+            try!(processor.geometry_collection_begin(geometries.len(), idx));
+            for (i, geometry) in geometries.iter().enumerate() {
+                try!(process_value(&geometry.value, processor, i));
+            }
+            try!(processor.geometry_collection_end(idx));
+        }
+    }
+    Ok(())
+}
+
+/// The nested container a [`GeometryBuilder`](struct.GeometryBuilder.html) is currently
+/// filling in. `linestring_end`/`polygon_end` consult the top frame to decide whether the
+/// ring/polygon they just finished is itself a standalone geometry or belongs to the
+/// container one level up.
+enum Frame {
+    Polygon(::position::PolygonType),
+    MultiLineString(Vec<::position::LineStringType>),
+    MultiPolygon(Vec<::position::PolygonType>),
+    GeometryCollection(Vec<ValueBase<f64>>),
+}
+
+/// A [`GeomProcessor`](trait.GeomProcessor.html) that reconstructs a `Geometry` from the
+/// same event stream `GeometryBase::process` emits, for round-tripping through another
+/// geometry backend without an intermediate format. All seven `Value` variants, including
+/// arbitrarily nested `GeometryCollection`s, are supported.
+///
+/// Only 2D output is supported: `xy` is the only coordinate callback `GeomProcessor`
+/// exposes, so every rebuilt position has exactly two components.
+pub struct GeometryBuilder {
+    pos_buf: Vec<PointTypeBase<f64>>,
+    frames: Vec<Frame>,
+    result: Option<ValueBase<f64>>,
+}
+
+impl GeometryBuilder {
+    pub fn new() -> Self {
+        GeometryBuilder {
+            pos_buf: Vec::new(),
+            frames: Vec::new(),
+            result: None,
+        }
+    }
+
+    /// Consumes the builder and returns the `Geometry` assembled from the processed
+    /// events, or `None` if nothing was ever processed.
+    pub fn build(self) -> Option<GeometryBase<f64>> {
+        self.result.map(GeometryBase::new)
+    }
+
+    /// Records a just-completed value: if we're inside a `GeometryCollection`, it's one of
+    /// its members; otherwise it's the (so far) overall result.
+    fn emit(&mut self, value: ValueBase<f64>) {
+        match self.frames.last_mut() {
+            Some(&mut Frame::GeometryCollection(ref mut values)) => values.push(value),
+            _ => self.result = Some(value),
+        }
+    }
+}
+
+impl GeomProcessor for GeometryBuilder {
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<(), Error> {
+        self.pos_buf.push([x, y].into());
+        Ok(())
+    }
+
+    fn point_begin(&mut self, _idx: usize) -> Result<(), Error> {
+        self.pos_buf.clear();
+        Ok(())
+    }
+
+    fn point_end(&mut self, _idx: usize) -> Result<(), Error> {
+        let pos = try!(self.pos_buf.drain(..).next().ok_or(Error::GeometryUnknownType));
+        self.emit(ValueBase::Point(pos));
+        Ok(())
+    }
+
+    fn multipoint_begin(&mut self, _size: usize, _idx: usize) -> Result<(), Error> {
+        self.pos_buf.clear();
+        Ok(())
+    }
+
+    fn multipoint_end(&mut self, _idx: usize) -> Result<(), Error> {
+        let positions = self.pos_buf.drain(..).collect();
+        self.emit(ValueBase::MultiPoint(positions));
+        Ok(())
+    }
+
+    fn linestring_begin(&mut self, _size: usize, _idx: usize) -> Result<(), Error> {
+        self.pos_buf.clear();
+        Ok(())
+    }
+
+    fn linestring_end(&mut self, _idx: usize) -> Result<(), Error> {
+        let line = self.pos_buf.drain(..).collect();
+        let mut standalone = None;
+        match self.frames.last_mut() {
+            Some(&mut Frame::Polygon(ref mut rings)) => rings.push(line),
+            Some(&mut Frame::MultiLineString(ref mut lines)) => lines.push(line),
+            _ => standalone = Some(line),
+        }
+        if let Some(line) = standalone {
+            self.emit(ValueBase::LineString(line));
+        }
+        Ok(())
+    }
+
+    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<(), Error> {
+        self.frames.push(Frame::MultiLineString(Vec::new()));
+        Ok(())
+    }
+
+    fn multilinestring_end(&mut self, _idx: usize) -> Result<(), Error> {
+        match self.frames.pop() {
+            Some(Frame::MultiLineString(lines)) => {
+                self.emit(ValueBase::MultiLineString(lines));
+                Ok(())
+            }
+            _ => Err(Error::GeometryUnknownType),
+        }
+    }
+
+    fn polygon_begin(&mut self, _size: usize, _idx: usize) -> Result<(), Error> {
+        self.frames.push(Frame::Polygon(Vec::new()));
+        Ok(())
+    }
+
+    fn polygon_end(&mut self, _idx: usize) -> Result<(), Error> {
+        let rings = match self.frames.pop() {
+            Some(Frame::Polygon(rings)) => rings,
+            _ => return Err(Error::GeometryUnknownType),
+        };
+        match self.frames.last_mut() {
+            Some(&mut Frame::MultiPolygon(ref mut polygons)) => polygons.push(rings),
+            _ => self.emit(ValueBase::Polygon(rings)),
+        }
+        Ok(())
+    }
+
+    fn multipolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<(), Error> {
+        self.frames.push(Frame::MultiPolygon(Vec::new()));
+        Ok(())
+    }
+
+    fn multipolygon_end(&mut self, _idx: usize) -> Result<(), Error> {
+        match self.frames.pop() {
+            Some(Frame::MultiPolygon(polygons)) => {
+                self.emit(ValueBase::MultiPolygon(polygons));
+                Ok(())
+            }
+            _ => Err(Error::GeometryUnknownType),
+        }
+    }
+
+    fn geometry_collection_begin(&mut self, _size: usize, _idx: usize) -> Result<(), Error> {
+        self.frames.push(Frame::GeometryCollection(Vec::new()));
+        Ok(())
+    }
+
+    fn geometry_collection_end(&mut self, _idx: usize) -> Result<(), Error> {
+        match self.frames.pop() {
+            Some(Frame::GeometryCollection(values)) => {
+                let geometries = values.into_iter().map(GeometryBase::new).collect();
+                self.emit(ValueBase::GeometryCollection(geometries));
+                Ok(())
+            }
+            _ => Err(Error::GeometryUnknownType),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::geometry::{GeometryBase, ValueBase};
+    use super::GeometryBuilder;
+
+    #[test]
+    fn builder_round_trips_nested_geometry_collection_and_multipolygon() {
+        let multipolygon = ValueBase::MultiPolygon(vec![
+            vec![vec![[0.0, 0.0].into(), [1.0, 0.0].into(), [1.0, 1.0].into(), [0.0, 0.0].into()]],
+            vec![vec![[10.0, 10.0].into(), [11.0, 10.0].into(), [11.0, 11.0].into(), [10.0, 10.0].into()]],
+        ]);
+        let point = ValueBase::Point([5.0, 5.0].into());
+        let original = GeometryBase::new(ValueBase::GeometryCollection(vec![
+            GeometryBase::new(multipolygon),
+            GeometryBase::new(point),
+        ]));
+
+        let mut builder = GeometryBuilder::new();
+        original.process(&mut builder).unwrap();
+        let rebuilt = builder.build().unwrap();
+
+        assert_eq!(rebuilt.value, original.value);
+    }
+}
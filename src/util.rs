@@ -0,0 +1,151 @@
+// Copyright 2015 The GeoRust Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers shared by every `FromObject` impl for pulling a GeoJSON geometry's members
+//! (`coordinates`, `geometries`, `bbox`, `crs`) out of a `JsonObject`.
+//!
+//! `read_position` fills a `PointTypeBase<T>` one coordinate at a time via
+//! [`PositionBase::push`](struct.PositionBase.html#method.push) instead of collecting the
+//! JSON array into a `Vec<T>` first and converting that afterwards — the conversion this
+//! crate parses the most of, by a wide margin, so the extra allocation per position was
+//! worth cutting.
+
+use ::geometry::GeometryBase;
+use ::json::{JsonObject, JsonValue};
+use ::position::{CoordFloat, PointTypeBase};
+use ::{Bbox, Crs, Error, FromObject};
+
+/// Expects `$object` to have a string `"type"` member, binding it to a `&str`, or returns
+/// `Err(Error::GeometryUnknownType)` from the enclosing function.
+macro_rules! expect_type {
+    ($object:expr) => {
+        match $object.get("type").and_then(|ty| ty.as_str()) {
+            Some(ty) => ty,
+            None => return Err(::Error::GeometryUnknownType),
+        }
+    }
+}
+
+fn invalid(reason: &str) -> Error {
+    Error::InvalidGeometryConversion(reason.into())
+}
+
+fn expect_array<'a>(value: &'a JsonValue, what: &str) -> Result<&'a Vec<JsonValue>, Error> {
+    value.as_array().ok_or_else(|| invalid(&format!("expected {} to be an array", what)))
+}
+
+fn expect_object<'a>(value: &'a JsonValue, what: &str) -> Result<&'a JsonObject, Error> {
+    value.as_object().ok_or_else(|| invalid(&format!("expected {} to be an object", what)))
+}
+
+fn coordinates_value(object: &JsonObject) -> Result<&JsonValue, Error> {
+    object.get("coordinates").ok_or_else(|| invalid("missing \"coordinates\" member"))
+}
+
+/// Reads a single GeoJSON position (a JSON array of numbers) directly into a
+/// `PointTypeBase<T>`, narrowing each coordinate to `T` as it's read.
+fn read_position<T: CoordFloat>(value: &JsonValue) -> Result<PointTypeBase<T>, Error> {
+    let coords = try!(expect_array(value, "a position"));
+    let mut position = PointTypeBase::new();
+    for coord in coords {
+        let n = try!(coord.as_f64().ok_or_else(|| invalid("expected a coordinate number")));
+        let narrowed = try!(T::from(n).ok_or_else(|| invalid("coordinate does not fit in the target numeric type")));
+        position.push(narrowed);
+    }
+    Ok(position)
+}
+
+/// `coordinates` is a single position, e.g. `Point`.
+pub fn get_coords_one_pos<T: CoordFloat>(object: &JsonObject) -> Result<PointTypeBase<T>, Error> {
+    read_position(try!(coordinates_value(object)))
+}
+
+/// `coordinates` is an array of positions, e.g. `MultiPoint`/`LineString`.
+pub fn get_coords_1d_pos<T: CoordFloat>(object: &JsonObject) -> Result<Vec<PointTypeBase<T>>, Error> {
+    let array = try!(expect_array(try!(coordinates_value(object)), "\"coordinates\""));
+    array.iter().map(read_position).collect()
+}
+
+/// `coordinates` is an array of arrays of positions, e.g. `MultiLineString`/`Polygon`.
+pub fn get_coords_2d_pos<T: CoordFloat>(object: &JsonObject) -> Result<Vec<Vec<PointTypeBase<T>>>, Error> {
+    let array = try!(expect_array(try!(coordinates_value(object)), "\"coordinates\""));
+    array.iter().map(|ring| {
+        let ring = try!(expect_array(ring, "a ring"));
+        ring.iter().map(read_position).collect()
+    }).collect()
+}
+
+/// `coordinates` is an array of arrays of arrays of positions, e.g. `MultiPolygon`.
+pub fn get_coords_3d_pos<T: CoordFloat>(object: &JsonObject) -> Result<Vec<Vec<Vec<PointTypeBase<T>>>>, Error> {
+    let array = try!(expect_array(try!(coordinates_value(object)), "\"coordinates\""));
+    array.iter().map(|polygon| {
+        let polygon = try!(expect_array(polygon, "a polygon"));
+        polygon.iter().map(|ring| {
+            let ring = try!(expect_array(ring, "a ring"));
+            ring.iter().map(read_position).collect()
+        }).collect()
+    }).collect()
+}
+
+/// `geometries` is an array of `Geometry` objects, for `GeometryCollection`.
+pub fn get_geometries<T: CoordFloat>(object: &JsonObject) -> Result<Vec<GeometryBase<T>>, Error> {
+    let geometries = object.get("geometries").ok_or_else(|| invalid("missing \"geometries\" member"));
+    let array = try!(expect_array(try!(geometries), "\"geometries\""));
+    array.iter().map(|geometry| {
+        GeometryBase::from_object(try!(expect_object(geometry, "a geometry")))
+    }).collect()
+}
+
+/// The optional `bbox` member, a flat array of numbers.
+pub fn get_bbox(object: &JsonObject) -> Result<Option<Bbox>, Error> {
+    match object.get("bbox") {
+        None => Ok(None),
+        Some(value) => {
+            let array = try!(expect_array(value, "\"bbox\""));
+            let values = try!(array.iter()
+                .map(|v| v.as_f64().ok_or_else(|| invalid("expected bbox values to be numbers")))
+                .collect::<Result<Vec<_>, _>>());
+            Ok(Some(values))
+        }
+    }
+}
+
+/// The optional `crs` member, a `{"type": ..., "properties": {...}}` object.
+pub fn get_crs(object: &JsonObject) -> Result<Option<Crs>, Error> {
+    let value = match object.get("crs") {
+        None => return Ok(None),
+        Some(value) => value,
+    };
+    let crs = try!(expect_object(value, "\"crs\""));
+    let ty = try!(crs.get("type").and_then(|v| v.as_str()).ok_or_else(|| invalid("missing \"crs.type\"")));
+    let properties = try!(expect_object(
+        try!(crs.get("properties").ok_or_else(|| invalid("missing \"crs.properties\""))),
+        "\"crs.properties\"",
+    ));
+
+    match ty {
+        "name" => {
+            let name = try!(properties.get("name").and_then(|v| v.as_str())
+                .ok_or_else(|| invalid("missing \"crs.properties.name\"")));
+            Ok(Some(Crs::Named { name: name.to_string() }))
+        }
+        "link" => {
+            let href = try!(properties.get("href").and_then(|v| v.as_str())
+                .ok_or_else(|| invalid("missing \"crs.properties.href\"")));
+            let type_ = properties.get("type").and_then(|v| v.as_str()).map(String::from);
+            Ok(Some(Crs::Linked { href: href.to_string(), type_: type_ }))
+        }
+        other => Err(invalid(&format!("unknown crs type \"{}\"", other))),
+    }
+}